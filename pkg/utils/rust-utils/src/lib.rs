@@ -15,15 +15,60 @@ pub mod json_utils {
     /// Merge multiple JSON objects
     pub fn merge_objects(objects: Vec<Value>) -> Result<Value> {
         let mut result = serde_json::Map::new();
-        
+
         for obj in objects {
             if let Value::Object(map) = obj {
                 result.extend(map);
             }
         }
-        
+
         Ok(Value::Object(result))
     }
+
+    /// Recursively merge `overlay` into `base`. Where both sides hold an object
+    /// at the same key, the objects are merged field by field; otherwise the
+    /// value from `overlay` wins.
+    pub fn deep_merge(base: Value, overlay: Value) -> Value {
+        match (base, overlay) {
+            (Value::Object(mut base_map), Value::Object(overlay_map)) => {
+                for (key, overlay_value) in overlay_map {
+                    let merged = match base_map.remove(&key) {
+                        Some(base_value) => deep_merge(base_value, overlay_value),
+                        None => overlay_value,
+                    };
+                    base_map.insert(key, merged);
+                }
+                Value::Object(base_map)
+            }
+            (_, overlay) => overlay,
+        }
+    }
+
+    /// Apply an RFC 7386 JSON Merge Patch `patch` to `target`, returning the
+    /// patched document. A `null` member in `patch` deletes the matching key;
+    /// any other member is merged in recursively, creating intermediate
+    /// objects as needed.
+    pub fn merge_patch(target: Value, patch: Value) -> Value {
+        let Value::Object(patch_map) = patch else {
+            return patch;
+        };
+
+        let mut target_map = match target {
+            Value::Object(map) => map,
+            _ => serde_json::Map::new(),
+        };
+
+        for (key, patch_value) in patch_map {
+            if patch_value.is_null() {
+                target_map.remove(&key);
+            } else {
+                let current = target_map.remove(&key).unwrap_or(Value::Null);
+                target_map.insert(key, merge_patch(current, patch_value));
+            }
+        }
+
+        Value::Object(target_map)
+    }
 }
 
 /// High-performance string utilities
@@ -53,7 +98,12 @@ pub mod hash_utils {
     use std::collections::hash_map::DefaultHasher;
     use std::hash::{Hash, Hasher};
 
-    /// Generate a fast hash for cache keys
+    /// Generate a fast, in-process hash for use as a map or cache key.
+    ///
+    /// This wraps `DefaultHasher`, whose output is *not* specified to be
+    /// stable across Rust versions, std releases, or platforms. Never
+    /// persist it, compare it between processes, or use it as a
+    /// content-addressed key — use [`content_hash`] for that instead.
     pub fn fast_hash<T: Hash>(input: &T) -> u64 {
         let mut hasher = DefaultHasher::new();
         input.hash(&mut hasher);
@@ -64,6 +114,45 @@ pub mod hash_utils {
     pub fn hash_string(input: &str) -> String {
         format!("{:x}", fast_hash(&input))
     }
+
+    /// Compute a stable, collision-resistant SHA-256 digest of `input` as a
+    /// lowercase hex string. Unlike [`fast_hash`], this digest is identical
+    /// across runs and platforms, so it can be persisted, compared between
+    /// nodes, and used as a content-addressed cache key.
+    pub fn content_hash(input: &[u8]) -> String {
+        let mut hasher = ContentHasher::new();
+        hasher.update(input);
+        hasher.finalize()
+    }
+
+    /// A streaming wrapper over the SHA-256 implementation backing
+    /// [`content_hash`], for hashing data incrementally without buffering
+    /// it all in memory first.
+    pub struct ContentHasher {
+        inner: sha2::Sha256,
+    }
+
+    impl ContentHasher {
+        pub fn new() -> Self {
+            Self {
+                inner: <sha2::Sha256 as sha2::Digest>::new(),
+            }
+        }
+
+        pub fn update(&mut self, bytes: &[u8]) {
+            sha2::Digest::update(&mut self.inner, bytes);
+        }
+
+        pub fn finalize(self) -> String {
+            sha2::Digest::finalize(self.inner).iter().map(|byte| format!("{:02x}", byte)).collect()
+        }
+    }
+
+    impl Default for ContentHasher {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
 }
 
 /// Performance measurement utilities
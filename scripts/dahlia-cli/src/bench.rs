@@ -0,0 +1,180 @@
+//! JSON-defined load generator workloads for the `bench` subcommand.
+
+use anyhow::{Context, Result};
+use reqwest::Client;
+use rust_utils::perf_utils::Timer;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+
+/// A named sequence of requests to replay against a target server.
+#[derive(Debug, Deserialize)]
+pub struct Workload {
+    pub name: String,
+    #[serde(default = "default_concurrency")]
+    pub concurrency: usize,
+    pub requests: Vec<WorkloadRequest>,
+}
+
+fn default_concurrency() -> usize {
+    1
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct WorkloadRequest {
+    pub method: String,
+    pub path: String,
+    #[serde(default)]
+    pub body: Option<Value>,
+    #[serde(default = "default_repeat")]
+    pub repeat: usize,
+}
+
+fn default_repeat() -> usize {
+    1
+}
+
+/// Latency and throughput summary for a completed workload run.
+#[derive(Debug, Serialize)]
+pub struct LatencyStats {
+    pub requests: usize,
+    pub failed: usize,
+    pub min_ms: f64,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+    pub throughput_rps: f64,
+}
+
+/// Load a workload definition from a JSON file on disk.
+pub fn load_workload(path: &Path) -> Result<Workload> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read workload file {}", path.display()))?;
+    serde_json::from_str(&contents)
+        .with_context(|| format!("failed to parse workload file {}", path.display()))
+}
+
+/// Flatten a workload's requests into the individual calls to issue,
+/// expanding each one `repeat` times (at least once).
+fn expand(workload: &Workload) -> Vec<WorkloadRequest> {
+    workload
+        .requests
+        .iter()
+        .flat_map(|req| std::iter::repeat(req.clone()).take(req.repeat.max(1)))
+        .collect()
+}
+
+/// Execute `workload` against `base_url`, running up to its configured
+/// `concurrency` requests at a time, and summarize the observed latencies.
+/// A failing individual request (bad status, timeout, connection error) is
+/// counted in [`LatencyStats::failed`] rather than aborting the whole run —
+/// a load generator has to tolerate the occasional bad response from a
+/// server under stress.
+pub async fn run_workload(client: &Client, base_url: &str, workload: &Workload) -> Result<LatencyStats> {
+    let calls = expand(workload);
+    let semaphore = Arc::new(Semaphore::new(workload.concurrency.max(1)));
+    let overall_timer = Timer::new();
+
+    let mut tasks = tokio::task::JoinSet::new();
+    for call in calls {
+        let client = client.clone();
+        let url = format!("{}{}", base_url, call.path);
+        let semaphore = semaphore.clone();
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await.expect("semaphore closed");
+            let timer = Timer::new();
+            let outcome: Result<u128> = async {
+                let method = call.method.parse::<reqwest::Method>()?;
+                let mut request = client.request(method, &url);
+                if let Some(body) = &call.body {
+                    request = request.json(body);
+                }
+                request.send().await?.error_for_status()?;
+                Ok(timer.elapsed_millis())
+            }
+            .await;
+            outcome
+        });
+    }
+
+    let mut latencies = Vec::new();
+    let mut failed = 0usize;
+    while let Some(joined) = tasks.join_next().await {
+        match joined? {
+            Ok(latency) => latencies.push(latency),
+            Err(err) => {
+                eprintln!("⚠️  bench request failed: {err}");
+                failed += 1;
+            }
+        }
+    }
+
+    Ok(summarize(latencies, failed, overall_timer.elapsed()))
+}
+
+fn summarize(mut latencies: Vec<u128>, failed: usize, elapsed: Duration) -> LatencyStats {
+    latencies.sort_unstable();
+
+    let percentile = |p: f64| -> f64 {
+        if latencies.is_empty() {
+            return 0.0;
+        }
+        let idx = ((p / 100.0) * (latencies.len() - 1) as f64).round() as usize;
+        latencies[idx] as f64
+    };
+
+    let sum: u128 = latencies.iter().sum();
+    let count = latencies.len().max(1);
+
+    LatencyStats {
+        requests: latencies.len(),
+        failed,
+        min_ms: *latencies.first().unwrap_or(&0) as f64,
+        mean_ms: sum as f64 / count as f64,
+        p50_ms: percentile(50.0),
+        p95_ms: percentile(95.0),
+        p99_ms: percentile(99.0),
+        max_ms: *latencies.last().unwrap_or(&0) as f64,
+        throughput_rps: latencies.len() as f64 / elapsed.as_secs_f64().max(0.001),
+    }
+}
+
+/// Print a human-readable summary table for a workload's results.
+pub fn print_report(name: &str, stats: &LatencyStats) {
+    println!("Workload: {}", name);
+    println!(
+        "{:<10} {:<8} {:<10} {:<10} {:<10} {:<10} {:<10} {:<12}",
+        "requests", "failed", "min(ms)", "mean(ms)", "p50(ms)", "p95(ms)", "p99(ms)", "throughput"
+    );
+    println!(
+        "{:<10} {:<8} {:<10.2} {:<10.2} {:<10.2} {:<10.2} {:<10.2} {:<12.2}",
+        stats.requests,
+        stats.failed,
+        stats.min_ms,
+        stats.mean_ms,
+        stats.p50_ms,
+        stats.p95_ms,
+        stats.p99_ms,
+        stats.throughput_rps
+    );
+}
+
+/// POST a machine-readable JSON report for `name` to a results-collection
+/// endpoint, for regression tracking across runs.
+pub async fn submit_report(client: &Client, endpoint: &str, name: &str, stats: &LatencyStats) -> Result<()> {
+    let payload = json!({ "workload": name, "stats": stats });
+    client
+        .post(endpoint)
+        .json(&payload)
+        .send()
+        .await
+        .with_context(|| format!("failed to submit bench report to {}", endpoint))?
+        .error_for_status()
+        .context("results endpoint rejected the bench report")?;
+    Ok(())
+}
@@ -0,0 +1,173 @@
+//! A small TTL cache wrapping `reqwest::Client`, persisted to a per-user
+//! cache directory so that repeated CLI invocations polling the same URL
+//! don't re-hit the network even though each invocation is a short-lived
+//! process.
+
+use reqwest::Client;
+use rust_utils::hash_utils::content_hash;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex;
+
+/// Default TTLs per endpoint kind: health churns fast, info rarely changes.
+pub const HEALTH_TTL: Duration = Duration::from_secs(2);
+pub const STATUS_TTL: Duration = Duration::from_secs(5);
+pub const INFO_TTL: Duration = Duration::from_secs(60);
+pub const METRICS_TTL: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    body: String,
+    inserted_unix_ms: u128,
+}
+
+impl CacheEntry {
+    fn age(&self) -> Duration {
+        let now_ms = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
+        Duration::from_millis(now_ms.saturating_sub(self.inserted_unix_ms) as u64)
+    }
+}
+
+/// A `reqwest::Client` wrapper that serves GET bodies from an on-disk cache
+/// file when a prior invocation fetched the same URL within `ttl`. The
+/// cache key is the URL's [`content_hash`], which — unlike `fast_hash` — is
+/// stable across runs and safe to persist.
+#[derive(Clone)]
+pub struct CachingClient {
+    client: Client,
+    ttl: Duration,
+    enabled: bool,
+    path: PathBuf,
+    entries: Arc<Mutex<HashMap<String, CacheEntry>>>,
+}
+
+impl CachingClient {
+    pub fn new(client: Client, ttl: Duration, enabled: bool) -> Self {
+        let path = cache_file_path();
+        let enabled = enabled && prepare_cache_dir(&path);
+        let entries = if enabled { load(&path) } else { HashMap::new() };
+        Self {
+            client,
+            ttl,
+            enabled,
+            path,
+            entries: Arc::new(Mutex::new(entries)),
+        }
+    }
+
+    /// GET `url` as text, returning a cached body if a previous invocation
+    /// fetched it within the TTL window; otherwise fetch, cache, and persist.
+    pub async fn get_text(&self, url: &str) -> reqwest::Result<String> {
+        if !self.enabled {
+            return self.client.get(url).send().await?.text().await;
+        }
+
+        let key = content_hash(url.as_bytes());
+        {
+            let entries = self.entries.lock().await;
+            if let Some(entry) = entries.get(&key) {
+                if entry.age() < self.ttl {
+                    return Ok(entry.body.clone());
+                }
+            }
+        }
+
+        let body = self.client.get(url).send().await?.text().await?;
+        let inserted_unix_ms = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
+
+        let mut entries = self.entries.lock().await;
+        entries.retain(|_, entry| entry.age() < self.ttl);
+        entries.insert(
+            key,
+            CacheEntry {
+                body: body.clone(),
+                inserted_unix_ms,
+            },
+        );
+        save(&self.path, &entries);
+
+        Ok(body)
+    }
+}
+
+/// The per-user cache file: `$XDG_CACHE_HOME/dahlia-cli/cache.json`, falling
+/// back to `$HOME/.cache` when `XDG_CACHE_HOME` is unset.
+fn cache_file_path() -> PathBuf {
+    let base = std::env::var("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|_| std::env::var("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .unwrap_or_else(|_| std::env::temp_dir());
+    base.join("dahlia-cli").join("cache.json")
+}
+
+/// Create the cache directory with owner-only permissions if it doesn't
+/// already exist. Refuses to use the directory if it is a symlink, which
+/// would let another local user on a multi-user box redirect the cache's
+/// reads and writes elsewhere; a symlinked cache file is rejected by `load`
+/// and `save`.
+fn prepare_cache_dir(path: &Path) -> bool {
+    let Some(dir) = path.parent() else { return false };
+
+    if is_symlink(dir) {
+        eprintln!("⚠️  cache directory {} is a symlink, disabling the response cache", dir.display());
+        return false;
+    }
+
+    if std::fs::create_dir_all(dir).is_err() {
+        return false;
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700));
+    }
+
+    true
+}
+
+fn is_symlink(path: &Path) -> bool {
+    std::fs::symlink_metadata(path).map(|metadata| metadata.file_type().is_symlink()).unwrap_or(false)
+}
+
+fn load(path: &Path) -> HashMap<String, CacheEntry> {
+    if is_symlink(path) {
+        eprintln!("⚠️  cache file {} is a symlink, ignoring it", path.display());
+        return HashMap::new();
+    }
+
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+fn save(path: &Path, entries: &HashMap<String, CacheEntry>) {
+    if is_symlink(path) {
+        eprintln!("⚠️  cache file {} is a symlink, refusing to write through it", path.display());
+        return;
+    }
+
+    if let Ok(contents) = serde_json::to_string(entries) {
+        let _ = write_restricted(path, &contents);
+    }
+}
+
+/// Write `contents` to `path`, creating it with owner-only read/write
+/// permissions so the cache isn't readable by other local users.
+#[cfg(unix)]
+fn write_restricted(path: &Path, contents: &str) -> std::io::Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+
+    let mut file = std::fs::OpenOptions::new().write(true).create(true).truncate(true).mode(0o600).open(path)?;
+    file.write_all(contents.as_bytes())
+}
+
+#[cfg(not(unix))]
+fn write_restricted(path: &Path, contents: &str) -> std::io::Result<()> {
+    std::fs::write(path, contents)
+}
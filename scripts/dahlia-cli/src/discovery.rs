@@ -0,0 +1,108 @@
+//! Consul-backed service discovery for locating and registering Dahlia instances.
+
+use anyhow::{Context, Result};
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::Duration;
+
+/// A single Consul catalog entry for a service instance.
+#[derive(Debug, Deserialize)]
+struct CatalogEntry {
+    #[serde(rename = "Node")]
+    node: NodeEntry,
+    #[serde(rename = "Service")]
+    service: ServiceEntry,
+    #[serde(rename = "Checks")]
+    checks: Vec<CheckEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NodeEntry {
+    #[serde(rename = "Address")]
+    address: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ServiceEntry {
+    #[serde(rename = "Port")]
+    port: u16,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckEntry {
+    #[serde(rename = "Status")]
+    status: String,
+}
+
+impl CatalogEntry {
+    fn is_healthy(&self) -> bool {
+        !self.checks.is_empty() && self.checks.iter().all(|check| check.status == "passing")
+    }
+
+    fn base_url(&self) -> String {
+        format!("http://{}:{}", self.node.address, self.service.port)
+    }
+}
+
+/// Resolve the base URLs of every healthy instance of `service` registered
+/// with the Consul catalog at `consul_addr`.
+pub async fn resolve_instances(client: &Client, consul_addr: &str, service: &str) -> Result<Vec<String>> {
+    let url = format!("{}/v1/health/service/{}?passing=true", consul_addr.trim_end_matches('/'), service);
+    let entries: Vec<CatalogEntry> = client
+        .get(&url)
+        .send()
+        .await
+        .with_context(|| format!("failed to reach consul catalog at {}", consul_addr))?
+        .error_for_status()
+        .with_context(|| format!("consul catalog at {} returned an error", consul_addr))?
+        .json()
+        .await
+        .context("failed to parse consul catalog response")?;
+
+    Ok(entries
+        .into_iter()
+        .filter(CatalogEntry::is_healthy)
+        .map(|entry| entry.base_url())
+        .collect())
+}
+
+/// Register the local server as a Consul service with a TTL health check,
+/// then loop forever re-posting a passing status at half the TTL interval so
+/// the registration stays alive.
+pub async fn register_and_report_health(
+    client: &Client,
+    consul_addr: &str,
+    service: &str,
+    service_id: &str,
+    port: u16,
+    ttl: Duration,
+) -> Result<()> {
+    let consul_addr = consul_addr.trim_end_matches('/');
+    let registration = serde_json::json!({
+        "ID": service_id,
+        "Name": service,
+        "Port": port,
+        "Check": {
+            "TTL": format!("{}s", ttl.as_secs()),
+            "DeregisterCriticalServiceAfter": "5m",
+        }
+    });
+
+    client
+        .put(&format!("{}/v1/agent/service/register", consul_addr))
+        .json(&registration)
+        .send()
+        .await
+        .context("failed to register service with consul")?
+        .error_for_status()
+        .context("consul rejected the service registration")?;
+
+    let check_id = format!("service:{}", service_id);
+    loop {
+        tokio::time::sleep(ttl / 2).await;
+        let pass_url = format!("{}/v1/agent/check/pass/{}", consul_addr, check_id);
+        if let Err(err) = client.put(&pass_url).send().await {
+            eprintln!("⚠️  failed to report health to consul: {}", err);
+        }
+    }
+}
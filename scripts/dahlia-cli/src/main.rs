@@ -2,6 +2,14 @@ use anyhow::Result;
 use clap::{Parser, Subcommand};
 use reqwest::Client;
 use serde_json::Value;
+use std::future::Future;
+use std::path::PathBuf;
+use std::time::Duration;
+
+mod bench;
+mod cache;
+mod discovery;
+mod prometheus;
 
 #[derive(Parser)]
 #[command(name = "dahlia")]
@@ -18,55 +26,288 @@ enum Commands {
     Health {
         #[arg(short, long, default_value = "http://localhost:8080")]
         url: String,
+        /// Consul catalog address to discover instances from, instead of --url
+        #[arg(long)]
+        consul: Option<String>,
+        /// Service name to resolve via Consul
+        #[arg(long, default_value = "dahlia")]
+        service: String,
+        /// Override the response cache TTL, in seconds
+        #[arg(long)]
+        ttl: Option<u64>,
+        /// Disable the response cache for this call
+        #[arg(long)]
+        no_cache: bool,
     },
     /// Get server status
     Status {
         #[arg(short, long, default_value = "http://localhost:8080")]
         url: String,
+        /// Consul catalog address to discover instances from, instead of --url
+        #[arg(long)]
+        consul: Option<String>,
+        /// Service name to resolve via Consul
+        #[arg(long, default_value = "dahlia")]
+        service: String,
+        /// Override the response cache TTL, in seconds
+        #[arg(long)]
+        ttl: Option<u64>,
+        /// Disable the response cache for this call
+        #[arg(long)]
+        no_cache: bool,
     },
     /// Get server information
     Info {
         #[arg(short, long, default_value = "http://localhost:8080")]
         url: String,
+        /// Override the response cache TTL, in seconds
+        #[arg(long)]
+        ttl: Option<u64>,
+        /// Disable the response cache for this call
+        #[arg(long)]
+        no_cache: bool,
     },
     /// Get server metrics
     Metrics {
         #[arg(short, long, default_value = "http://localhost:8080")]
         url: String,
+        /// Consul catalog address to discover instances from, instead of --url
+        #[arg(long)]
+        consul: Option<String>,
+        /// Service name to resolve via Consul
+        #[arg(long, default_value = "dahlia")]
+        service: String,
+        /// Override the response cache TTL, in seconds
+        #[arg(long)]
+        ttl: Option<u64>,
+        /// Disable the response cache for this call
+        #[arg(long)]
+        no_cache: bool,
+        /// Parse the Prometheus exposition format into structured JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Register this server with Consul and keep its TTL health check alive
+    Register {
+        /// Consul catalog address, e.g. http://localhost:8500
+        #[arg(long)]
+        consul: String,
+        /// Service name to register under
+        #[arg(long, default_value = "dahlia")]
+        service: String,
+        /// Unique ID for this instance within the service
+        #[arg(long)]
+        service_id: String,
+        /// Port this server is listening on
+        #[arg(long)]
+        port: u16,
+        /// TTL health check interval, in seconds
+        #[arg(long, default_value_t = 30)]
+        ttl_secs: u64,
+    },
+    /// Run load-test workloads defined in JSON files
+    Bench {
+        /// One or more workload JSON files to execute
+        #[arg(required = true)]
+        workloads: Vec<PathBuf>,
+        #[arg(short, long, default_value = "http://localhost:8080")]
+        url: String,
+        /// Optional results-collection endpoint to POST each JSON report to
+        #[arg(long)]
+        report_url: Option<String>,
     },
 }
 
+/// Resolve the set of instance base URLs a command should target: either the
+/// single `--url`, or every healthy instance Consul reports for `service`.
+async fn resolve_targets(client: &Client, url: &str, consul: &Option<String>, service: &str) -> Result<Vec<String>> {
+    match consul {
+        Some(addr) => discovery::resolve_instances(client, addr, service).await,
+        None => Ok(vec![url.to_string()]),
+    }
+}
+
+/// Print a simple two-column, left-aligned table of node -> result.
+fn print_table(rows: &[(String, String)]) {
+    let width = rows.iter().map(|(node, _)| node.len()).max().unwrap_or(0);
+    for (node, result) in rows {
+        println!("{:width$}  {}", node, result, width = width);
+    }
+}
+
+/// Run `fetch(node)` concurrently for every node, returning each node's
+/// result paired with its URL once all of them have completed. Shared by
+/// every multi-node command (`Health`, `Status`, `Metrics`) so the
+/// spawn-and-join boilerplate lives in exactly one place.
+async fn fan_out<T, F, Fut>(nodes: Vec<String>, fetch: F) -> Result<Vec<(String, Result<T>)>>
+where
+    F: Fn(String) -> Fut,
+    Fut: Future<Output = Result<T>> + Send + 'static,
+    T: Send + 'static,
+{
+    let mut tasks = tokio::task::JoinSet::new();
+    for node in nodes {
+        let fut = fetch(node.clone());
+        tasks.spawn(async move { (node, fut.await) });
+    }
+
+    let mut results = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        results.push(joined?);
+    }
+    Ok(results)
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
     let client = Client::new();
 
     match cli.command {
-        Commands::Health { url } => {
-            let response = client.get(&format!("{}/health", url)).send().await?;
-            let json: Value = response.json().await?;
+        Commands::Health { url, consul, service, ttl, no_cache } => {
+            let nodes = resolve_targets(&client, &url, &consul, &service).await?;
+            let cache = cache::CachingClient::new(
+                client.clone(),
+                ttl.map(Duration::from_secs).unwrap_or(cache::HEALTH_TTL),
+                !no_cache,
+            );
             println!("🌸 Health Check:");
-            println!("{}", serde_json::to_string_pretty(&json)?);
+
+            let results = fan_out(nodes, |node| {
+                let cache = cache.clone();
+                async move {
+                    let body = cache.get_text(&format!("{}/health", node)).await?;
+                    Ok(serde_json::from_str::<Value>(&body)?)
+                }
+            })
+            .await?;
+
+            let mut rows = Vec::new();
+            for (node, outcome) in results {
+                let cell = match outcome {
+                    Ok(json) => serde_json::to_string(&json)?,
+                    Err(err) => format!("error: {err}"),
+                };
+                rows.push((node, cell));
+            }
+            print_table(&rows);
         }
-        Commands::Status { url } => {
-            let response = client.get(&format!("{}/api/v1/status", url)).send().await?;
-            let json: Value = response.json().await?;
+        Commands::Status { url, consul, service, ttl, no_cache } => {
+            let nodes = resolve_targets(&client, &url, &consul, &service).await?;
+            let cache = cache::CachingClient::new(
+                client.clone(),
+                ttl.map(Duration::from_secs).unwrap_or(cache::STATUS_TTL),
+                !no_cache,
+            );
             println!("📊 Server Status:");
-            println!("{}", serde_json::to_string_pretty(&json)?);
+
+            let results = fan_out(nodes, |node| {
+                let cache = cache.clone();
+                async move {
+                    let body = cache.get_text(&format!("{}/api/v1/status", node)).await?;
+                    Ok(serde_json::from_str::<Value>(&body)?)
+                }
+            })
+            .await?;
+
+            let mut rows = Vec::new();
+            for (node, outcome) in results {
+                let cell = match outcome {
+                    Ok(json) => serde_json::to_string(&json)?,
+                    Err(err) => format!("error: {err}"),
+                };
+                rows.push((node, cell));
+            }
+            print_table(&rows);
         }
-        Commands::Info { url } => {
-            let response = client.get(&format!("{}/api/v1/info", url)).send().await?;
-            let json: Value = response.json().await?;
+        Commands::Info { url, ttl, no_cache } => {
+            let cache = cache::CachingClient::new(
+                client.clone(),
+                ttl.map(Duration::from_secs).unwrap_or(cache::INFO_TTL),
+                !no_cache,
+            );
+            let body = cache.get_text(&format!("{}/api/v1/info", url)).await?;
+            let json: Value = serde_json::from_str(&body)?;
             println!("ℹ️  Server Info:");
             println!("{}", serde_json::to_string_pretty(&json)?);
         }
-        Commands::Metrics { url } => {
-            let response = client.get(&format!("{}/metrics", url)).send().await?;
-            let text = response.text().await?;
-            println!("📈 Server Metrics:");
-            println!("{}", text);
+        Commands::Metrics { url, consul, service, ttl, no_cache, json } => {
+            let nodes = resolve_targets(&client, &url, &consul, &service).await?;
+            let cache = cache::CachingClient::new(
+                client.clone(),
+                ttl.map(Duration::from_secs).unwrap_or(cache::METRICS_TTL),
+                !no_cache,
+            );
+            if !json {
+                println!("📈 Server Metrics:");
+            }
+
+            let results = fan_out(nodes, |node| {
+                let cache = cache.clone();
+                async move { Ok(cache.get_text(&format!("{}/metrics", node)).await?) }
+            })
+            .await?;
+
+            let mut rows = Vec::new();
+            let mut parsed = serde_json::Map::new();
+            for (node, outcome) in results {
+                match outcome {
+                    Ok(text) if json => match prometheus::parse(&text) {
+                        Ok(value) => {
+                            parsed.insert(node.clone(), value);
+                            rows.push((node, "ok".to_string()));
+                        }
+                        Err(err) => rows.push((node, format!("parse error: {err}"))),
+                    },
+                    Ok(text) => rows.push((node, text)),
+                    Err(err) => rows.push((node, format!("error: {err}"))),
+                }
+            }
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&Value::Object(parsed))?);
+            } else {
+                print_table(&rows);
+            }
+        }
+        Commands::Register {
+            consul,
+            service,
+            service_id,
+            port,
+            ttl_secs,
+        } => {
+            println!("🔗 Registering '{}' ({}) with consul at {}", service, service_id, consul);
+            discovery::register_and_report_health(
+                &client,
+                &consul,
+                &service,
+                &service_id,
+                port,
+                Duration::from_secs(ttl_secs),
+            )
+            .await?;
+        }
+        Commands::Bench { workloads, url, report_url } => {
+            for path in workloads {
+                let workload = bench::load_workload(&path)?;
+                let stats = bench::run_workload(&client, &url, &workload).await?;
+
+                bench::print_report(&workload.name, &stats);
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "workload": workload.name,
+                        "stats": stats,
+                    }))?
+                );
+
+                if let Some(endpoint) = &report_url {
+                    bench::submit_report(&client, endpoint, &workload.name, &stats).await?;
+                }
+            }
         }
     }
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,201 @@
+//! Parser for the Prometheus text exposition format, turning raw `/metrics`
+//! output into a structured `serde_json::Value` grouped by metric family.
+
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Default)]
+struct Family {
+    help: Option<String>,
+    type_: Option<String>,
+    samples: Vec<Value>,
+}
+
+struct Sample {
+    name: String,
+    labels: Map<String, Value>,
+    value: f64,
+    timestamp: Option<i64>,
+}
+
+/// Metric types whose samples the exposition format reports under suffixed
+/// forms (`_bucket`/`_sum`/`_count`) of the family's declared base name.
+const SUFFIXED_FAMILY_TYPES: [&str; 2] = ["histogram", "summary"];
+
+/// Parse Prometheus exposition-format text into a JSON object keyed by
+/// metric name, each holding its `help`, `type`, and the list of samples
+/// (with labels, value, and optional timestamp) collected for it.
+pub fn parse(input: &str) -> Result<Value> {
+    let mut families: BTreeMap<String, Family> = BTreeMap::new();
+
+    // Register HELP/TYPE metadata before handling any sample lines: a
+    // histogram or summary's samples arrive under type-specific suffixes
+    // (`_bucket`/`_sum`/`_count`) and need the base family's declared type
+    // to know they should fold back into it rather than form their own.
+    for (lineno, raw_line) in input.lines().enumerate() {
+        let line = raw_line.trim();
+
+        if let Some(rest) = line.strip_prefix("# HELP ") {
+            let (name, help) = rest
+                .split_once(' ')
+                .with_context(|| format!("malformed HELP comment on line {}", lineno + 1))?;
+            families.entry(name.to_string()).or_default().help = Some(help.to_string());
+        } else if let Some(rest) = line.strip_prefix("# TYPE ") {
+            let (name, metric_type) = rest
+                .split_once(' ')
+                .with_context(|| format!("malformed TYPE comment on line {}", lineno + 1))?;
+            families.entry(name.to_string()).or_default().type_ = Some(metric_type.to_string());
+        }
+    }
+
+    for (lineno, raw_line) in input.lines().enumerate() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let sample = parse_sample(line).with_context(|| format!("malformed metric sample on line {}", lineno + 1))?;
+        let key = family_key(&sample.name, &families);
+        families.entry(key).or_default().samples.push(json!({
+            "labels": sample.labels,
+            "value": sample.value,
+            "timestamp": sample.timestamp,
+        }));
+    }
+
+    let mut result = Map::new();
+    for (name, family) in families {
+        result.insert(
+            name,
+            json!({
+                "help": family.help,
+                "type": family.type_,
+                "samples": family.samples,
+            }),
+        );
+    }
+
+    Ok(Value::Object(result))
+}
+
+/// Resolve the family a sample belongs to: if `sample_name` carries a
+/// histogram/summary suffix (`_bucket`, `_sum`, `_count`) and the base name
+/// with that suffix stripped was declared as such a type, fold the sample
+/// into the base family instead of creating an orphan one.
+fn family_key(sample_name: &str, families: &BTreeMap<String, Family>) -> String {
+    for suffix in ["_bucket", "_sum", "_count"] {
+        if let Some(base) = sample_name.strip_suffix(suffix) {
+            if let Some(family) = families.get(base) {
+                if family.type_.as_deref().is_some_and(|t| SUFFIXED_FAMILY_TYPES.contains(&t)) {
+                    return base.to_string();
+                }
+            }
+        }
+    }
+
+    sample_name.to_string()
+}
+
+/// Parse one non-comment exposition line: `name{label="v",...} value [timestamp]`.
+fn parse_sample(line: &str) -> Result<Sample> {
+    let space_idx = line.find(' ').context("expected a value after the metric name")?;
+    let name_and_labels = &line[..space_idx];
+    let value_and_ts = line[space_idx + 1..].trim();
+
+    let (name, labels) = match name_and_labels.find('{') {
+        Some(brace_idx) => {
+            let name = name_and_labels[..brace_idx].to_string();
+            let label_str = name_and_labels[brace_idx + 1..]
+                .strip_suffix('}')
+                .context("unterminated label set")?;
+            (name, parse_labels(label_str)?)
+        }
+        None => (name_and_labels.to_string(), Map::new()),
+    };
+
+    let mut parts = value_and_ts.split_whitespace();
+    let value: f64 = parts.next().context("missing metric value")?.parse().context("metric value is not a number")?;
+    let timestamp = parts.next().map(|t| t.parse()).transpose().context("timestamp is not an integer")?;
+
+    Ok(Sample { name, labels, value, timestamp })
+}
+
+fn parse_labels(label_str: &str) -> Result<Map<String, Value>> {
+    let mut labels = Map::new();
+    if label_str.is_empty() {
+        return Ok(labels);
+    }
+
+    for pair in split_label_pairs(label_str) {
+        let (key, raw_value) = pair.split_once('=').with_context(|| format!("malformed label pair: {}", pair))?;
+        let value = raw_value.trim();
+        let quoted = value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .with_context(|| format!("label value must be quoted: {}", raw_value))?;
+        labels.insert(key.trim().to_string(), Value::String(unescape_label_value(quoted)));
+    }
+
+    Ok(labels)
+}
+
+/// Undo the exposition format's label-value escaping in one left-to-right
+/// pass: `\"` -> `"`, `\\` -> `\`, `\n` -> a newline. An escape followed by
+/// anything else is passed through as-is.
+fn unescape_label_value(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            result.push(ch);
+            continue;
+        }
+
+        match chars.next() {
+            Some('"') => result.push('"'),
+            Some('\\') => result.push('\\'),
+            Some('n') => result.push('\n'),
+            Some(other) => {
+                result.push('\\');
+                result.push(other);
+            }
+            None => result.push('\\'),
+        }
+    }
+
+    result
+}
+
+/// Split a label list on commas that aren't inside a quoted label value.
+/// A `"` immediately preceded by an unescaped backslash is a literal quote
+/// inside the value, not the end of it, so it must not toggle `in_quotes`.
+fn split_label_pairs(input: &str) -> Vec<&str> {
+    let mut pairs = Vec::new();
+    let mut in_quotes = false;
+    let mut escaped = false;
+    let mut start = 0;
+
+    for (idx, ch) in input.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match ch {
+            '\\' if in_quotes => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => {
+                pairs.push(input[start..idx].trim());
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    let tail = input[start..].trim();
+    if !tail.is_empty() {
+        pairs.push(tail);
+    }
+
+    pairs
+}